@@ -0,0 +1,283 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crossbeam_channel::{Receiver, Sender};
+use futures_channel::oneshot;
+use futures_util::io::{AsyncRead, AsyncSeek, AsyncWrite};
+use rusqlite::blob::Blob;
+use rusqlite::DatabaseName;
+
+use crate::client::Client;
+use crate::error::Error;
+
+enum BlobOp {
+    ReadAt {
+        offset: u64,
+        len: usize,
+        resp: oneshot::Sender<io::Result<Vec<u8>>>,
+    },
+    WriteAt {
+        offset: u64,
+        data: Vec<u8>,
+        resp: oneshot::Sender<io::Result<()>>,
+    },
+    Close,
+}
+
+fn run_blob_session(mut blob: Blob<'_>, rx: &Receiver<BlobOp>) {
+    use std::io::{Read, Seek, SeekFrom, Write};
+    while let Ok(op) = rx.recv() {
+        match op {
+            BlobOp::ReadAt { offset, len, resp } => {
+                let result = (|| -> io::Result<Vec<u8>> {
+                    blob.seek(SeekFrom::Start(offset))?;
+                    let mut buf = vec![0u8; len];
+                    let n = blob.read(&mut buf)?;
+                    buf.truncate(n);
+                    Ok(buf)
+                })();
+                let _ = resp.send(result);
+            }
+            BlobOp::WriteAt { offset, data, resp } => {
+                let result = (|| -> io::Result<()> {
+                    blob.seek(SeekFrom::Start(offset))?;
+                    blob.write_all(&data)
+                })();
+                let _ = resp.send(result);
+            }
+            BlobOp::Close => break,
+        }
+    }
+}
+
+enum State {
+    Idle,
+    Reading(oneshot::Receiver<io::Result<Vec<u8>>>),
+    Writing(oneshot::Receiver<io::Result<()>>, usize),
+}
+
+/// An open, incremental handle onto a single BLOB column/row, implementing
+/// [`AsyncRead`], [`AsyncWrite`] and [`AsyncSeek`] so large binary values can be
+/// streamed rather than loaded whole.
+///
+/// Returned by [`Client::open_blob`]. For as long as the handle is alive, its
+/// background connection is reserved for positioned reads/writes against this
+/// BLOB and does not serve any other `conn`/`conn_blocking` call.
+pub struct BlobHandle {
+    tx: Sender<BlobOp>,
+    pos: u64,
+    size: u64,
+    state: State,
+}
+
+impl BlobHandle {
+    /// The size, in bytes, of the BLOB this handle was opened against.
+    pub fn len(&self) -> u64 {
+        self.size
+    }
+
+    /// Whether the BLOB this handle was opened against is empty.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+}
+
+impl Drop for BlobHandle {
+    fn drop(&mut self) {
+        let _ = self.tx.send(BlobOp::Close);
+    }
+}
+
+fn closed_error() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "blob handle's connection is closed")
+}
+
+impl AsyncRead for BlobHandle {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            match &mut self.state {
+                State::Idle => {
+                    let (resp, rx) = oneshot::channel();
+                    let op = BlobOp::ReadAt {
+                        offset: self.pos,
+                        len: buf.len(),
+                        resp,
+                    };
+                    if self.tx.send(op).is_err() {
+                        return Poll::Ready(Err(closed_error()));
+                    }
+                    self.state = State::Reading(rx);
+                }
+                State::Reading(rx) => {
+                    return match Pin::new(rx).poll(cx) {
+                        Poll::Ready(Ok(Ok(data))) => {
+                            // `buf` may be smaller than the in-flight request if the
+                            // future that issued it was dropped and polled again with
+                            // a different buffer; copy only what fits.
+                            let n = data.len().min(buf.len());
+                            buf[..n].copy_from_slice(&data[..n]);
+                            self.pos += n as u64;
+                            self.state = State::Idle;
+                            Poll::Ready(Ok(n))
+                        }
+                        Poll::Ready(Ok(Err(err))) => {
+                            self.state = State::Idle;
+                            Poll::Ready(Err(err))
+                        }
+                        Poll::Ready(Err(_)) => {
+                            self.state = State::Idle;
+                            Poll::Ready(Err(closed_error()))
+                        }
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+                // A write issued before this read was cancelled without waiting for
+                // its response; the background session still owes us one reply
+                // before it can serve the read. Drain it, then loop to issue the read.
+                State::Writing(rx, _) => match Pin::new(rx).poll(cx) {
+                    Poll::Ready(_) => {
+                        self.state = State::Idle;
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+            }
+        }
+    }
+}
+
+impl AsyncWrite for BlobHandle {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            match &mut self.state {
+                State::Idle => {
+                    let (resp, rx) = oneshot::channel();
+                    let op = BlobOp::WriteAt {
+                        offset: self.pos,
+                        data: buf.to_vec(),
+                        resp,
+                    };
+                    if self.tx.send(op).is_err() {
+                        return Poll::Ready(Err(closed_error()));
+                    }
+                    self.state = State::Writing(rx, buf.len());
+                }
+                State::Writing(rx, len) => {
+                    let len = *len;
+                    return match Pin::new(rx).poll(cx) {
+                        Poll::Ready(Ok(Ok(()))) => {
+                            self.pos += len as u64;
+                            self.size = self.size.max(self.pos);
+                            self.state = State::Idle;
+                            Poll::Ready(Ok(len))
+                        }
+                        Poll::Ready(Ok(Err(err))) => {
+                            self.state = State::Idle;
+                            Poll::Ready(Err(err))
+                        }
+                        Poll::Ready(Err(_)) => {
+                            self.state = State::Idle;
+                            Poll::Ready(Err(closed_error()))
+                        }
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+                // A read issued before this write was cancelled without waiting for
+                // its response; drain it first, then loop to issue the write.
+                State::Reading(rx) => match Pin::new(rx).poll(cx) {
+                    Poll::Ready(_) => {
+                        self.state = State::Idle;
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let _ = self.tx.send(BlobOp::Close);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for BlobHandle {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: io::SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let new_pos = match pos {
+            io::SeekFrom::Start(offset) => offset as i64,
+            io::SeekFrom::End(offset) => self.size as i64 + offset,
+            io::SeekFrom::Current(offset) => self.pos as i64 + offset,
+        };
+        if new_pos < 0 {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "seek to a negative position",
+            )));
+        }
+        self.pos = new_pos as u64;
+        Poll::Ready(Ok(self.pos))
+    }
+}
+
+impl Client {
+    /// Open an incremental handle onto `table.column` at `rowid` in database `db`
+    /// (e.g. `"main"`), via [`rusqlite::Connection::blob_open`].
+    ///
+    /// The returned [`BlobHandle`] reserves this connection for the duration of
+    /// its lifetime: while it's open, no other `conn`/`conn_blocking` call is
+    /// served on this `Client`.
+    pub async fn open_blob(
+        &self,
+        db: &str,
+        table: &str,
+        column: &str,
+        rowid: i64,
+        read_only: bool,
+    ) -> Result<BlobHandle, Error> {
+        let (blob_tx, blob_rx) = crossbeam_channel::unbounded();
+        let (opened_tx, opened_rx) = oneshot::channel();
+        let db = db.to_string();
+        let table = table.to_string();
+        let column = column.to_string();
+        self.execute(Box::new(move |conn| {
+            let db_name = if db == "main" {
+                DatabaseName::Main
+            } else {
+                DatabaseName::Attached(&db)
+            };
+            let blob = match conn.blob_open(db_name, &table, &column, rowid, read_only) {
+                Ok(blob) => blob,
+                Err(err) => {
+                    let _ = opened_tx.send(Err(Error::from(err)));
+                    return;
+                }
+            };
+            let size = blob.len() as u64;
+            let _ = opened_tx.send(Ok(size));
+            run_blob_session(blob, &blob_rx);
+        }))?;
+        let size = opened_rx.await.map_err(|_| Error::Closed)??;
+        Ok(BlobHandle {
+            tx: blob_tx,
+            pos: 0,
+            size,
+            state: State::Idle,
+        })
+    }
+}
@@ -0,0 +1,546 @@
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, Sender};
+use futures_channel::oneshot;
+use rusqlite::backup::Backup;
+use rusqlite::{Connection, OpenFlags};
+
+use crate::error::Error;
+use crate::extension::{self, ExtensionToLoad};
+use crate::migrations::{self, Migration};
+
+/// The mode by which SQLite acquires and releases locks on the main database.
+///
+/// See the [SQLite docs](https://www.sqlite.org/pragma.html#pragma_journal_mode) for
+/// details on each mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JournalMode {
+    Delete,
+    Truncate,
+    Persist,
+    Memory,
+    #[default]
+    Wal,
+    Off,
+}
+
+impl JournalMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            JournalMode::Delete => "DELETE",
+            JournalMode::Truncate => "TRUNCATE",
+            JournalMode::Persist => "PERSIST",
+            JournalMode::Memory => "MEMORY",
+            JournalMode::Wal => "WAL",
+            JournalMode::Off => "OFF",
+        }
+    }
+}
+
+/// A policy for transparently retrying `conn`/`conn_blocking` closures that fail
+/// with `SQLITE_BUSY`/`SQLITE_LOCKED`, set via [`ClientBuilder::retry_on_busy`].
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub(crate) fn new(max_retries: u32, backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            backoff,
+        }
+    }
+}
+
+fn is_busy(err: &rusqlite::Error) -> bool {
+    matches!(
+        err,
+        rusqlite::Error::SqliteFailure(ffi_err, _)
+            if matches!(
+                ffi_err.code,
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+            )
+    )
+}
+
+/// Call `func`, retrying on `SQLITE_BUSY`/`SQLITE_LOCKED` according to
+/// `retry_policy`, sleeping `backoff` between attempts. With no policy configured,
+/// `func` is called exactly once.
+fn call_with_retry<F, T>(
+    conn: &Connection,
+    func: &F,
+    retry_policy: Option<RetryPolicy>,
+) -> rusqlite::Result<T>
+where
+    F: Fn(&Connection) -> rusqlite::Result<T>,
+{
+    let Some(policy) = retry_policy else {
+        return func(conn);
+    };
+    let mut attempt = 0;
+    loop {
+        match func(conn) {
+            Err(err) if attempt < policy.max_retries && is_busy(&err) => {
+                attempt += 1;
+                thread::sleep(policy.backoff);
+            }
+            result => return result,
+        }
+    }
+}
+
+pub(crate) type CallFn = Box<dyn FnOnce(&mut Connection) + Send + 'static>;
+
+enum Message {
+    Execute(CallFn),
+    Close(Box<dyn FnOnce() + Send + 'static>),
+}
+
+/// Options controlling how an online backup or restore is carried out.
+///
+/// A backup/restore is performed in a loop of small `step`s so that a large, live
+/// database can be copied without holding a lock on the source for the whole
+/// duration. See [`Client::backup`] and [`Client::restore`].
+#[derive(Clone, Copy, Debug)]
+pub struct BackupOptions {
+    pages_per_step: i32,
+    step_sleep: Duration,
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        Self {
+            pages_per_step: 100,
+            step_sleep: Duration::from_millis(250),
+        }
+    }
+}
+
+impl BackupOptions {
+    /// Create a new `BackupOptions` with the default pages-per-step and sleep.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many pages to copy per `step`. Smaller values reduce writer starvation
+    /// at the cost of a longer overall backup.
+    pub fn pages_per_step(mut self, pages_per_step: i32) -> Self {
+        self.pages_per_step = pages_per_step;
+        self
+    }
+
+    /// How long to sleep between steps, and how long to wait before retrying a
+    /// step that failed with `SQLITE_BUSY`/`SQLITE_LOCKED`.
+    pub fn step_sleep(mut self, step_sleep: Duration) -> Self {
+        self.step_sleep = step_sleep;
+        self
+    }
+}
+
+/// Progress reported between backup/restore steps as `(remaining_pages, total_pages)`.
+pub type ProgressFn = dyn FnMut(i32, i32) + Send + 'static;
+
+fn run_backup(
+    src: &Connection,
+    dst: &mut Connection,
+    options: BackupOptions,
+    mut progress: Option<Box<ProgressFn>>,
+) -> rusqlite::Result<()> {
+    let backup = Backup::new(src, dst)?;
+    loop {
+        match backup.step(options.pages_per_step)? {
+            rusqlite::backup::StepResult::Done => return Ok(()),
+            rusqlite::backup::StepResult::More => {
+                let progress_info = backup.progress();
+                if let Some(progress) = progress.as_mut() {
+                    progress(progress_info.remaining, progress_info.pagecount);
+                }
+                thread::sleep(options.step_sleep);
+            }
+            rusqlite::backup::StepResult::Busy | rusqlite::backup::StepResult::Locked => {
+                thread::sleep(options.step_sleep);
+            }
+            // `StepResult` is `#[non_exhaustive]`; treat any future variant the
+            // same as a transient busy/locked result.
+            _ => thread::sleep(options.step_sleep),
+        }
+    }
+}
+
+/// A handle to a background sqlite3 connection that can be called concurrently from
+/// any thread in your program.
+pub struct Client {
+    tx: Sender<Message>,
+    retry_policy: Option<RetryPolicy>,
+}
+
+impl Client {
+    fn send(&self, message: Message) -> Result<(), Error> {
+        self.tx.send(message).map_err(|_| Error::Closed)
+    }
+
+    /// Queue a closure to run on the background thread without waiting for its
+    /// result. Used by submodules (e.g. streaming queries) that need to forward
+    /// results through a channel of their own rather than a single `oneshot`.
+    pub(crate) fn execute(&self, func: CallFn) -> Result<(), Error> {
+        self.send(Message::Execute(func))
+    }
+
+    /// Invoke the provided function with a [`rusqlite::Connection`], returning the
+    /// result asynchronously.
+    ///
+    /// If [`ClientBuilder::retry_on_busy`] was configured, `func` may be invoked
+    /// more than once: a `SQLITE_BUSY`/`SQLITE_LOCKED` result is retried with
+    /// backoff, transparently to the caller.
+    pub async fn conn<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: Fn(&Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let retry_policy = self.retry_policy;
+        self.conn_once(move |conn| call_with_retry(conn, &func, retry_policy))
+            .await
+    }
+
+    /// Invoke the provided function with a [`rusqlite::Connection`], blocking the
+    /// current thread until it completes.
+    ///
+    /// If [`ClientBuilder::retry_on_busy`] was configured, `func` may be invoked
+    /// more than once: a `SQLITE_BUSY`/`SQLITE_LOCKED` result is retried with
+    /// backoff, transparently to the caller.
+    pub fn conn_blocking<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: Fn(&Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let retry_policy = self.retry_policy;
+        self.conn_once_blocking(move |conn| call_with_retry(conn, &func, retry_policy))
+    }
+
+    /// Invoke `func` exactly once, bypassing any configured retry policy. Used
+    /// internally for work that either shouldn't be retried (hook installation) or
+    /// already implements its own busy/retry handling (backup/restore).
+    pub(crate) async fn conn_once<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        self.send(Message::Execute(Box::new(move |conn| {
+            let _ = tx.send(func(conn).map_err(Error::from));
+        })))?;
+        rx.await.map_err(|_| Error::Closed)?
+    }
+
+    /// Blocking variant of [`Client::conn_once`].
+    fn conn_once_blocking<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        self.send(Message::Execute(Box::new(move |conn| {
+            let _ = tx.send(func(conn).map_err(Error::from));
+        })))?;
+        rx.recv().map_err(|_| Error::Closed)?
+    }
+
+    /// Like [`Client::conn_once`], but hands `func` a `&mut Connection`. Needed for
+    /// [`rusqlite::backup::Backup`], which requires exclusive access to the
+    /// destination connection.
+    async fn conn_once_mut<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        self.send(Message::Execute(Box::new(move |conn| {
+            let _ = tx.send(func(conn).map_err(Error::from));
+        })))?;
+        rx.await.map_err(|_| Error::Closed)?
+    }
+
+    /// Blocking variant of [`Client::conn_once_mut`].
+    fn conn_once_mut_blocking<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        self.send(Message::Execute(Box::new(move |conn| {
+            let _ = tx.send(func(conn).map_err(Error::from));
+        })))?;
+        rx.recv().map_err(|_| Error::Closed)?
+    }
+
+    /// Take an online, hot backup of this connection's database into `dest_path`,
+    /// copying [`BackupOptions::pages_per_step`] pages at a time and sleeping
+    /// [`BackupOptions::step_sleep`] between steps (including as a retry delay on
+    /// `SQLITE_BUSY`/`SQLITE_LOCKED`).
+    ///
+    /// `progress` is invoked between steps with `(remaining_pages, total_pages)`.
+    pub async fn backup<P: AsRef<Path>>(
+        &self,
+        dest_path: P,
+        options: BackupOptions,
+        progress: Option<Box<ProgressFn>>,
+    ) -> Result<(), Error> {
+        let dest_path = dest_path.as_ref().to_path_buf();
+        self.conn_once(move |conn| {
+            let mut dest = Connection::open(&dest_path)?;
+            run_backup(conn, &mut dest, options, progress)
+        })
+        .await
+    }
+
+    /// Blocking variant of [`Client::backup`].
+    pub fn backup_blocking<P: AsRef<Path>>(
+        &self,
+        dest_path: P,
+        options: BackupOptions,
+        progress: Option<Box<ProgressFn>>,
+    ) -> Result<(), Error> {
+        let dest_path = dest_path.as_ref().to_path_buf();
+        self.conn_once_blocking(move |conn| {
+            let mut dest = Connection::open(&dest_path)?;
+            run_backup(conn, &mut dest, options, progress)
+        })
+    }
+
+    /// Restore this connection's database from the backup at `src_path`, copying
+    /// [`BackupOptions::pages_per_step`] pages at a time and sleeping
+    /// [`BackupOptions::step_sleep`] between steps (including as a retry delay on
+    /// `SQLITE_BUSY`/`SQLITE_LOCKED`).
+    ///
+    /// `progress` is invoked between steps with `(remaining_pages, total_pages)`.
+    pub async fn restore<P: AsRef<Path>>(
+        &self,
+        src_path: P,
+        options: BackupOptions,
+        progress: Option<Box<ProgressFn>>,
+    ) -> Result<(), Error> {
+        let src_path = src_path.as_ref().to_path_buf();
+        self.conn_once_mut(move |conn| {
+            let src = Connection::open(&src_path)?;
+            run_backup(&src, conn, options, progress)
+        })
+        .await
+    }
+
+    /// Blocking variant of [`Client::restore`].
+    pub fn restore_blocking<P: AsRef<Path>>(
+        &self,
+        src_path: P,
+        options: BackupOptions,
+        progress: Option<Box<ProgressFn>>,
+    ) -> Result<(), Error> {
+        let src_path = src_path.as_ref().to_path_buf();
+        self.conn_once_mut_blocking(move |conn| {
+            let src = Connection::open(&src_path)?;
+            run_backup(&src, conn, options, progress)
+        })
+    }
+
+    /// Close the background connection, waiting for all pending work to finish.
+    pub async fn close(self) -> Result<(), Error> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Message::Close(Box::new(move || {
+            let _ = tx.send(());
+        })))?;
+        rx.await.map_err(|_| Error::Closed)
+    }
+
+    /// Blocking variant of [`Client::close`].
+    pub fn close_blocking(self) -> Result<(), Error> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        self.send(Message::Close(Box::new(move || {
+            let _ = tx.send(());
+        })))?;
+        rx.recv().map_err(|_| Error::Closed)
+    }
+}
+
+/// A builder for a [`Client`].
+#[derive(Default)]
+pub struct ClientBuilder {
+    path: Option<PathBuf>,
+    flags: OpenFlags,
+    journal_mode: Option<JournalMode>,
+    migrations: Vec<Migration>,
+    extensions: Vec<ExtensionToLoad>,
+    busy_timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+}
+
+impl ClientBuilder {
+    /// Create a new `ClientBuilder`. Defaults to an in-memory database.
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            flags: OpenFlags::default(),
+            journal_mode: None,
+            migrations: Vec::new(),
+            extensions: Vec::new(),
+            busy_timeout: None,
+            retry_policy: None,
+        }
+    }
+
+    /// Path to the sqlite3 database file to open.
+    pub fn path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Flags passed to [`rusqlite::Connection::open_with_flags`].
+    pub fn flags(mut self, flags: OpenFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Journal mode to set on the connection after opening it.
+    pub fn journal_mode(mut self, journal_mode: JournalMode) -> Self {
+        self.journal_mode = Some(journal_mode);
+        self
+    }
+
+    /// Ordered schema migrations to apply, based on `PRAGMA user_version`, once the
+    /// connection is opened and before it serves any other work. See [`Migration`],
+    /// or build the list with [`Migration::from_directory`]/[`Migration::from_embedded`]
+    /// instead of listing inline SQL strings by hand.
+    pub fn migrations(mut self, migrations: Vec<Migration>) -> Self {
+        self.migrations = migrations;
+        self
+    }
+
+    /// Load a SQLite extension once the connection is opened, before it serves any
+    /// other work. `entry_point` is the extension's entry point function name, or
+    /// `None` to use SQLite's default resolution. Can be called multiple times to
+    /// load several extensions, in order.
+    pub fn load_extension<P: AsRef<Path>>(mut self, dylib_path: P, entry_point: Option<&str>) -> Self {
+        self.extensions.push(ExtensionToLoad {
+            dylib_path: dylib_path.as_ref().to_path_buf(),
+            entry_point: entry_point.map(str::to_string),
+        });
+        self
+    }
+
+    /// How long SQLite should wait on a locked table/database before returning
+    /// `SQLITE_BUSY`, via [`rusqlite::Connection::busy_timeout`]. In WAL mode with
+    /// multiple writer connections (e.g. a [`crate::Pool`]), this is the first line
+    /// of defense against spurious busy errors; pair it with
+    /// [`ClientBuilder::retry_on_busy`] for a policy that also survives timeouts
+    /// that are still too short under heavy contention.
+    pub fn busy_timeout(mut self, busy_timeout: Duration) -> Self {
+        self.busy_timeout = Some(busy_timeout);
+        self
+    }
+
+    /// Transparently retry `conn`/`conn_blocking` closures that fail with
+    /// `SQLITE_BUSY`/`SQLITE_LOCKED`, up to `max_retries` times, sleeping `backoff`
+    /// between attempts.
+    ///
+    /// A retry re-runs the *entire* closure from the start, not just the
+    /// statement that returned busy. If the closure runs several statements
+    /// outside of its own transaction, a retry can re-apply whichever of them
+    /// already succeeded before the busy error. Closures configured under a retry
+    /// policy should either be idempotent or wrap their statements in their own
+    /// transaction (e.g. `conn.transaction()`), so a retried attempt starts from
+    /// the same state as the first one.
+    pub fn retry_on_busy(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.retry_policy = Some(RetryPolicy::new(max_retries, backoff));
+        self
+    }
+
+    /// Open the client, spawning the background thread and opening the connection
+    /// on it.
+    pub async fn open(self) -> Result<Client, Error> {
+        let (tx, rx) = oneshot::channel();
+        self.open_with(move |result| {
+            let _ = tx.send(result);
+        });
+        rx.await.map_err(|_| Error::Closed)?
+    }
+
+    /// Blocking variant of [`ClientBuilder::open`].
+    pub fn open_blocking(self) -> Result<Client, Error> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        self.open_with(move |result| {
+            let _ = tx.send(result);
+        });
+        rx.recv().map_err(|_| Error::Closed)?
+    }
+
+    fn open_with<F>(self, on_open: F)
+    where
+        F: FnOnce(Result<Client, Error>) + Send + 'static,
+    {
+        let ClientBuilder {
+            path,
+            flags,
+            journal_mode,
+            migrations,
+            extensions,
+            busy_timeout,
+            retry_policy,
+        } = self;
+        let (tx, rx) = crossbeam_channel::unbounded();
+        thread::spawn(move || {
+            match open_connection(
+                path.as_deref(),
+                flags,
+                journal_mode,
+                busy_timeout,
+                &migrations,
+                &extensions,
+            ) {
+                Ok(mut conn) => {
+                    on_open(Ok(Client { tx, retry_policy }));
+                    run(&mut conn, &rx);
+                }
+                Err(err) => on_open(Err(Error::from(err))),
+            }
+        });
+    }
+}
+
+pub(crate) fn open_connection(
+    path: Option<&Path>,
+    flags: OpenFlags,
+    journal_mode: Option<JournalMode>,
+    busy_timeout: Option<Duration>,
+    migrations: &[Migration],
+    extensions: &[ExtensionToLoad],
+) -> rusqlite::Result<Connection> {
+    let mut conn = match path {
+        Some(path) => Connection::open_with_flags(path, flags)?,
+        None => Connection::open_in_memory_with_flags(flags)?,
+    };
+    if let Some(busy_timeout) = busy_timeout {
+        conn.busy_timeout(busy_timeout)?;
+    }
+    if let Some(journal_mode) = journal_mode {
+        conn.pragma_update(None, "journal_mode", journal_mode.as_str())?;
+    }
+    migrations::run_migrations(&mut conn, migrations)?;
+    for ext in extensions {
+        extension::load_extension(&conn, ext)?;
+    }
+    Ok(conn)
+}
+
+fn run(conn: &mut Connection, rx: &Receiver<Message>) {
+    while let Ok(message) = rx.recv() {
+        match message {
+            Message::Execute(func) => func(conn),
+            Message::Close(ack) => {
+                ack();
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Support for loading the [CR-SQLite](https://github.com/vlcn-io/cr-sqlite)
+//! extension, which turns ordinary tables into conflict-free replicated (CRDT)
+//! tables.
+//!
+//! This crate does not vendor or embed the CR-SQLite shared library itself: there
+//! is one compiled artifact per OS/architecture, and shipping all of them would
+//! bloat every consumer regardless of whether they use CR-SQLite. Instead,
+//! [`ClientBuilder::load_crsqlite_bytes`] takes the bytes for the *caller's*
+//! platform and materializes them to a temp file, so the caller is free to obtain
+//! them however fits their build (a `build.rs` that compiles CR-SQLite from
+//! source, a downloaded release asset checked into their own repo, etc.).
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::client::ClientBuilder;
+use crate::error::Error;
+use crate::pool::PoolBuilder;
+
+/// Entry point function CR-SQLite expects to be resolved by SQLite's extension
+/// loader.
+const CRSQLITE_ENTRY_POINT: &str = "sqlite3_crsqlite_init";
+
+/// The platform-specific file name CR-SQLite's build publishes its shared library
+/// under.
+fn platform_file_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "crsqlite.dll"
+    } else if cfg!(target_os = "macos") {
+        "crsqlite.dylib"
+    } else {
+        "crsqlite.so"
+    }
+}
+
+/// Materialize `library_bytes` (the contents of a CR-SQLite shared library for the
+/// current platform) to a fresh temporary directory and return the resulting path.
+///
+/// The temporary directory is intentionally leaked for the lifetime of the
+/// process: the library must remain on disk for as long as any connection that
+/// loaded it is alive.
+fn materialize_library(library_bytes: &[u8]) -> std::io::Result<PathBuf> {
+    static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "obeli-sk-deps-async-sqlite-crsqlite-{}-{}",
+        std::process::id(),
+        NEXT_DIR.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(platform_file_name());
+    fs::write(&path, library_bytes)?;
+    Ok(path)
+}
+
+impl ClientBuilder {
+    /// Load the CR-SQLite extension from caller-supplied shared library bytes, so
+    /// that ordinary tables can be turned into conflict-free replicated (CRDT)
+    /// tables with `SELECT crsql_as_crr('table')`.
+    ///
+    /// This crate does not bundle the CR-SQLite library itself (see the
+    /// [module docs](self)); `library_bytes` must be the compiled shared library
+    /// for the current platform, e.g. produced by a `build.rs` or embedded with
+    /// `include_bytes!` from a downloaded release asset.
+    ///
+    /// The bytes are materialized to a temporary file once, at builder time, and
+    /// loaded like any other extension via [`ClientBuilder::load_extension`].
+    pub fn load_crsqlite_bytes(self, library_bytes: &[u8]) -> Result<Self, Error> {
+        let path = materialize_library(library_bytes)?;
+        Ok(self.load_extension(path, Some(CRSQLITE_ENTRY_POINT)))
+    }
+}
+
+impl PoolBuilder {
+    /// Load the CR-SQLite extension from caller-supplied shared library bytes on
+    /// every connection in the pool. Behaves like
+    /// [`ClientBuilder::load_crsqlite_bytes`], via [`PoolBuilder::load_extension`].
+    ///
+    /// CR-SQLite's CRDT tables are specifically meant for merging concurrent
+    /// writes from multiple writers, making this the common case for a `Pool`
+    /// (which typically holds several writer-capable connections in
+    /// [`JournalMode::Wal`](crate::JournalMode::Wal)).
+    pub fn load_crsqlite_bytes(self, library_bytes: &[u8]) -> Result<Self, Error> {
+        let path = materialize_library(library_bytes)?;
+        Ok(self.load_extension(path, Some(CRSQLITE_ENTRY_POINT)))
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+/// The error type for all fallible operations in this crate.
+#[derive(Debug)]
+pub enum Error {
+    /// An error returned by the underlying [`rusqlite`] library.
+    Rusqlite(rusqlite::Error),
+    /// The background connection (or pool) has already been closed.
+    Closed,
+    /// An I/O error unrelated to SQLite itself, e.g. while writing caller-supplied
+    /// extension bytes to disk or reading migration files.
+    Io(std::io::Error),
+    /// A migration file or embedded entry name did not start with a numeric
+    /// version prefix. See [`Migration::from_directory`] and
+    /// [`Migration::from_embedded`].
+    ///
+    /// [`Migration::from_directory`]: crate::Migration::from_directory
+    /// [`Migration::from_embedded`]: crate::Migration::from_embedded
+    InvalidMigrationName(String),
+    /// [`PoolBuilder::migrations`] was set on an in-memory pool (no [`PoolBuilder::path`]).
+    /// Each connection in an in-memory `Pool` opens its own independent, empty
+    /// database, so migrations applied to one throwaway connection would never be
+    /// visible to the rest; use a file-backed path, or migrate a single
+    /// [`Client`](crate::Client) instead.
+    ///
+    /// [`PoolBuilder::migrations`]: crate::PoolBuilder::migrations
+    /// [`PoolBuilder::path`]: crate::PoolBuilder::path
+    InMemoryMigrationsUnsupported,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Rusqlite(err) => write!(f, "{err}"),
+            Error::Closed => write!(f, "connection is closed"),
+            Error::Io(err) => write!(f, "{err}"),
+            Error::InvalidMigrationName(name) => {
+                write!(f, "migration name {name:?} does not start with a numeric version prefix")
+            }
+            Error::InMemoryMigrationsUnsupported => {
+                write!(f, "migrations require a file-backed path; each connection in an in-memory pool has its own independent database")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Rusqlite(err) => Some(err),
+            Error::Closed => None,
+            Error::Io(err) => Some(err),
+            Error::InvalidMigrationName(_) => None,
+            Error::InMemoryMigrationsUnsupported => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        Error::Rusqlite(err)
+    }
+}
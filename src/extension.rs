@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+
+use crate::client::Client;
+use crate::error::Error;
+
+/// A single SQLite extension to load when a connection is opened, as passed to
+/// [`ClientBuilder::load_extension`](crate::ClientBuilder::load_extension).
+#[derive(Clone, Debug)]
+pub(crate) struct ExtensionToLoad {
+    pub(crate) dylib_path: PathBuf,
+    pub(crate) entry_point: Option<String>,
+}
+
+/// Load a single extension on `conn`, toggling `load_extension` capability on for
+/// just the duration of the call, as recommended by the `rusqlite` docs.
+pub(crate) fn load_extension(
+    conn: &rusqlite::Connection,
+    extension: &ExtensionToLoad,
+) -> rusqlite::Result<()> {
+    unsafe {
+        // If enabling fails, there is nothing to disable; propagate as-is.
+        conn.load_extension_enable()?;
+        let result = conn.load_extension(&extension.dylib_path, extension.entry_point.as_deref());
+        let disable_result = conn.load_extension_disable();
+        // Always attempt to disable the capability, but prefer surfacing the load
+        // error over the disable error: a failure to disable is unusual (and still
+        // reported, via the rusqlite::Result) while a failure to load is the
+        // caller's actionable signal.
+        result.and(disable_result)
+    }
+}
+
+impl Client {
+    /// Load a SQLite extension on the background connection, right now.
+    ///
+    /// `entry_point` is the name of the extension's entry point function; pass
+    /// `None` to use SQLite's default resolution (`sqlite3_extension_init`, derived
+    /// from the file name).
+    pub async fn load_extension<P: AsRef<Path>>(
+        &self,
+        dylib_path: P,
+        entry_point: Option<&str>,
+    ) -> Result<(), Error> {
+        let extension = ExtensionToLoad {
+            dylib_path: dylib_path.as_ref().to_path_buf(),
+            entry_point: entry_point.map(str::to_string),
+        };
+        self.conn(move |conn| load_extension(conn, &extension))
+            .await
+    }
+
+    /// Blocking variant of [`Client::load_extension`].
+    pub fn load_extension_blocking<P: AsRef<Path>>(
+        &self,
+        dylib_path: P,
+        entry_point: Option<&str>,
+    ) -> Result<(), Error> {
+        let extension = ExtensionToLoad {
+            dylib_path: dylib_path.as_ref().to_path_buf(),
+            entry_point: entry_point.map(str::to_string),
+        };
+        self.conn_blocking(move |conn| load_extension(conn, &extension))
+    }
+}
@@ -0,0 +1,135 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_channel::mpsc::{self, UnboundedReceiver};
+use futures_util::stream::Stream;
+use rusqlite::hooks::Action;
+
+use crate::client::Client;
+use crate::error::Error;
+
+/// The kind of row-level change reported by an [`UpdateEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdateAction {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl From<Action> for UpdateAction {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::SQLITE_INSERT => UpdateAction::Insert,
+            Action::SQLITE_DELETE => UpdateAction::Delete,
+            // rusqlite's `Action` is non-exhaustive and only ever reports these
+            // three variants to the update hook; treat anything else as an update.
+            _ => UpdateAction::Update,
+        }
+    }
+}
+
+/// A single row-level change, reported by [`Client::subscribe_updates`].
+#[derive(Clone, Debug)]
+pub struct UpdateEvent {
+    pub action: UpdateAction,
+    pub db: String,
+    pub table: String,
+    pub rowid: i64,
+}
+
+macro_rules! event_stream {
+    ($(#[$meta:meta])* $name:ident, $item:ty) => {
+        $(#[$meta])*
+        ///
+        /// The background thread only ever performs a non-blocking
+        /// `unbounded_send` into this stream's channel; if the stream has been
+        /// dropped (no subscriber attached), the send simply fails and the event
+        /// is discarded rather than blocking or slowing down the connection.
+        ///
+        /// A connection has at most one hook of this kind installed at a time:
+        /// subscribing again overwrites the previous hook (and drops its
+        /// sender), so the earlier stream yields the events it already buffered
+        /// and then ends, without an error. Fan-out to multiple subscribers isn't
+        /// supported; if you need it, forward events from a single subscription
+        /// into as many consumers as you need.
+        pub struct $name {
+            rx: UnboundedReceiver<$item>,
+        }
+
+        impl Stream for $name {
+            type Item = $item;
+
+            fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                Pin::new(&mut self.rx).poll_next(cx)
+            }
+        }
+    };
+}
+
+event_stream!(
+    /// A stream of [`UpdateEvent`]s, returned by [`Client::subscribe_updates`].
+    UpdateStream,
+    UpdateEvent
+);
+event_stream!(
+    /// A stream of commit notifications, returned by [`Client::subscribe_commits`].
+    CommitStream,
+    ()
+);
+event_stream!(
+    /// A stream of rollback notifications, returned by
+    /// [`Client::subscribe_rollbacks`].
+    RollbackStream,
+    ()
+);
+
+impl Client {
+    /// Subscribe to row-level insert/update/delete notifications from the
+    /// background connection via [`rusqlite`]'s `update_hook`.
+    pub async fn subscribe_updates(&self) -> Result<UpdateStream, Error> {
+        let (tx, rx) = mpsc::unbounded();
+        self.conn_once(move |conn| {
+            conn.update_hook(Some(move |action, db: &str, table: &str, rowid| {
+                let _ = tx.unbounded_send(UpdateEvent {
+                    action: UpdateAction::from(action),
+                    db: db.to_string(),
+                    table: table.to_string(),
+                    rowid,
+                });
+            }));
+            Ok(())
+        })
+        .await?;
+        Ok(UpdateStream { rx })
+    }
+
+    /// Subscribe to commit notifications from the background connection via
+    /// [`rusqlite`]'s `commit_hook`. Commits are never vetoed by this
+    /// subscription; the underlying hook always returns `false`.
+    pub async fn subscribe_commits(&self) -> Result<CommitStream, Error> {
+        let (tx, rx) = mpsc::unbounded();
+        self.conn_once(move |conn| {
+            conn.commit_hook(Some(move || {
+                let _ = tx.unbounded_send(());
+                false
+            }));
+            Ok(())
+        })
+        .await?;
+        Ok(CommitStream { rx })
+    }
+
+    /// Subscribe to rollback notifications from the background connection via
+    /// [`rusqlite`]'s `rollback_hook`.
+    pub async fn subscribe_rollbacks(&self) -> Result<RollbackStream, Error> {
+        let (tx, rx) = mpsc::unbounded();
+        self.conn_once(move |conn| {
+            conn.rollback_hook(Some(move || {
+                let _ = tx.unbounded_send(());
+            }));
+            Ok(())
+        })
+        .await?;
+        Ok(RollbackStream { rx })
+    }
+}
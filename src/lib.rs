@@ -70,10 +70,20 @@
 
 pub use rusqlite;
 
+mod blob;
 mod client;
+mod crsqlite;
 mod error;
+mod extension;
+mod hooks;
+mod migrations;
 mod pool;
+mod stream;
 
-pub use client::{Client, ClientBuilder, JournalMode};
+pub use blob::BlobHandle;
+pub use client::{BackupOptions, Client, ClientBuilder, JournalMode, ProgressFn};
 pub use error::Error;
+pub use hooks::{CommitStream, RollbackStream, UpdateAction, UpdateEvent, UpdateStream};
+pub use migrations::Migration;
 pub use pool::{Pool, PoolBuilder};
+pub use stream::QueryStream;
@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::error::Error;
+
+/// A single, versioned schema migration.
+///
+/// Migrations are identified by a monotonically increasing `version`, tracked via
+/// SQLite's `PRAGMA user_version`. See [`ClientBuilder::migrations`] and
+/// [`PoolBuilder::migrations`].
+///
+/// [`ClientBuilder::migrations`]: crate::ClientBuilder::migrations
+/// [`PoolBuilder::migrations`]: crate::PoolBuilder::migrations
+#[derive(Clone, Debug)]
+pub struct Migration {
+    pub version: u32,
+    pub up: String,
+}
+
+impl Migration {
+    /// Create a migration that runs `up` when the database's `user_version` is
+    /// below `version`.
+    pub fn new(version: u32, up: impl Into<String>) -> Self {
+        Self {
+            version,
+            up: up.into(),
+        }
+    }
+
+    /// Build migrations from an embedded set of `(name, sql)` pairs, e.g. gathered
+    /// at compile time with one `include_str!` per file in a migrations directory.
+    ///
+    /// Each `name` must start with a numeric version prefix (e.g. `"0001_init.sql"`);
+    /// everything after the leading digits is ignored and only used to keep
+    /// migration files readable on disk.
+    pub fn from_embedded<'a, I>(files: I) -> Result<Vec<Migration>, Error>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        files
+            .into_iter()
+            .map(|(name, up)| Ok(Migration::new(version_prefix(name)?, up)))
+            .collect()
+    }
+
+    /// Build migrations from every `*.sql` file directly inside `dir`, each named
+    /// with a numeric version prefix (e.g. `"0001_init.sql"`); the rest of the file
+    /// name is ignored. Files are read eagerly, so `dir` need not remain available
+    /// afterwards.
+    pub fn from_directory(dir: impl AsRef<Path>) -> Result<Vec<Migration>, Error> {
+        let mut migrations = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("sql") {
+                continue;
+            }
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| Error::InvalidMigrationName(path.to_string_lossy().into_owned()))?;
+            let version = version_prefix(name)?;
+            let up = std::fs::read_to_string(&path)?;
+            migrations.push(Migration::new(version, up));
+        }
+        Ok(migrations)
+    }
+}
+
+/// Parse the leading run of ASCII digits off a migration file/entry `name` as its
+/// version, per [`Migration::from_directory`] and [`Migration::from_embedded`].
+fn version_prefix(name: &str) -> Result<u32, Error> {
+    let digits: String = name.chars().take_while(char::is_ascii_digit).collect();
+    if digits.is_empty() {
+        return Err(Error::InvalidMigrationName(name.to_string()));
+    }
+    digits
+        .parse()
+        .map_err(|_| Error::InvalidMigrationName(name.to_string()))
+}
+
+/// Apply every migration with a version greater than the database's current
+/// `PRAGMA user_version`, in ascending order, inside a single transaction. Bumps
+/// `user_version` to the highest applied version. If any migration fails, the
+/// whole batch is rolled back, leaving `user_version` unchanged.
+pub(crate) fn run_migrations(conn: &mut Connection, migrations: &[Migration]) -> rusqlite::Result<()> {
+    if migrations.is_empty() {
+        return Ok(());
+    }
+    let current_version: u32 = conn.query_row("PRAGMA user_version", (), |row| row.get(0))?;
+    let mut pending: Vec<&Migration> = migrations
+        .iter()
+        .filter(|migration| migration.version > current_version)
+        .collect();
+    if pending.is_empty() {
+        return Ok(());
+    }
+    pending.sort_by_key(|migration| migration.version);
+
+    let tx = conn.transaction()?;
+    for migration in &pending {
+        tx.execute_batch(&migration.up)?;
+    }
+    let latest_version = pending.last().expect("pending is non-empty").version;
+    tx.pragma_update(None, "user_version", latest_version)?;
+    tx.commit()
+}
@@ -0,0 +1,297 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use rusqlite::{Connection, OpenFlags};
+
+use crate::client::{open_connection, BackupOptions, Client, ClientBuilder, JournalMode, ProgressFn};
+use crate::error::Error;
+use crate::extension::ExtensionToLoad;
+use crate::migrations::Migration;
+
+const DEFAULT_NUM_CONNS: usize = 4;
+
+/// A collection of background sqlite3 connections that can be called concurrently
+/// from any thread in your program.
+///
+/// Connections are handed out round-robin; since SQLite serializes writers anyway,
+/// a `Pool` is mainly useful for parallelizing readers (typically paired with
+/// [`JournalMode::Wal`]).
+pub struct Pool {
+    clients: Vec<Client>,
+    next: AtomicUsize,
+}
+
+impl Pool {
+    fn client(&self) -> &Client {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.clients.len();
+        &self.clients[index]
+    }
+
+    /// Invoke the provided function with a [`rusqlite::Connection`] from the pool,
+    /// returning the result asynchronously.
+    pub async fn conn<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: Fn(&Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.client().conn(func).await
+    }
+
+    /// Invoke the provided function with a [`rusqlite::Connection`] from the pool,
+    /// blocking the current thread until it completes.
+    pub fn conn_blocking<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: Fn(&Connection) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.client().conn_blocking(func)
+    }
+
+    /// Take an online, hot backup of the pool's database into `dest_path`, via one
+    /// of the pooled connections. See [`Client::backup`].
+    pub async fn backup<P: AsRef<Path>>(
+        &self,
+        dest_path: P,
+        options: BackupOptions,
+        progress: Option<Box<ProgressFn>>,
+    ) -> Result<(), Error> {
+        self.client().backup(dest_path, options, progress).await
+    }
+
+    /// Blocking variant of [`Pool::backup`].
+    pub fn backup_blocking<P: AsRef<Path>>(
+        &self,
+        dest_path: P,
+        options: BackupOptions,
+        progress: Option<Box<ProgressFn>>,
+    ) -> Result<(), Error> {
+        self.client().backup_blocking(dest_path, options, progress)
+    }
+
+    /// Restore the pool's database from the backup at `src_path`, via one of the
+    /// pooled connections. See [`Client::restore`].
+    ///
+    /// Every other pooled connection keeps its own handle open on the same file
+    /// for the whole pool's lifetime, so this only overwrites the on-disk database
+    /// out from under connections that aren't actively serving a statement at the
+    /// time; prefer restoring before opening the `Pool`, or only while it is
+    /// otherwise idle.
+    pub async fn restore<P: AsRef<Path>>(
+        &self,
+        src_path: P,
+        options: BackupOptions,
+        progress: Option<Box<ProgressFn>>,
+    ) -> Result<(), Error> {
+        self.client().restore(src_path, options, progress).await
+    }
+
+    /// Blocking variant of [`Pool::restore`].
+    pub fn restore_blocking<P: AsRef<Path>>(
+        &self,
+        src_path: P,
+        options: BackupOptions,
+        progress: Option<Box<ProgressFn>>,
+    ) -> Result<(), Error> {
+        self.client().restore_blocking(src_path, options, progress)
+    }
+
+    /// Close every connection in the pool, waiting for all pending work to finish.
+    pub async fn close(self) -> Result<(), Error> {
+        for client in self.clients {
+            client.close().await?;
+        }
+        Ok(())
+    }
+
+    /// Blocking variant of [`Pool::close`].
+    pub fn close_blocking(self) -> Result<(), Error> {
+        for client in self.clients {
+            client.close_blocking()?;
+        }
+        Ok(())
+    }
+}
+
+/// A builder for a [`Pool`].
+pub struct PoolBuilder {
+    path: Option<PathBuf>,
+    flags: OpenFlags,
+    journal_mode: Option<JournalMode>,
+    num_conns: usize,
+    migrations: Vec<Migration>,
+    extensions: Vec<ExtensionToLoad>,
+    busy_timeout: Option<Duration>,
+    retry_policy: Option<(u32, Duration)>,
+}
+
+impl Default for PoolBuilder {
+    fn default() -> Self {
+        Self {
+            path: None,
+            flags: OpenFlags::default(),
+            journal_mode: None,
+            num_conns: DEFAULT_NUM_CONNS,
+            migrations: Vec::new(),
+            extensions: Vec::new(),
+            busy_timeout: None,
+            retry_policy: None,
+        }
+    }
+}
+
+impl PoolBuilder {
+    /// Create a new `PoolBuilder`. Defaults to an in-memory database and
+    /// `num_conns(4)`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Path to the sqlite3 database file to open.
+    pub fn path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Flags passed to [`rusqlite::Connection::open_with_flags`] for every
+    /// connection in the pool.
+    pub fn flags(mut self, flags: OpenFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Journal mode to set on every connection in the pool after opening it.
+    pub fn journal_mode(mut self, journal_mode: JournalMode) -> Self {
+        self.journal_mode = Some(journal_mode);
+        self
+    }
+
+    /// How many background connections to open. Defaults to 4.
+    pub fn num_conns(mut self, num_conns: usize) -> Self {
+        self.num_conns = num_conns;
+        self
+    }
+
+    /// Ordered schema migrations to apply, based on `PRAGMA user_version`. Applied
+    /// exactly once, on a dedicated connection, before any of the pool's
+    /// connections begin serving. See [`Migration`].
+    ///
+    /// Requires [`PoolBuilder::path`]: an in-memory pool has no shared database for
+    /// a dedicated migration connection to hand off to the rest, so
+    /// [`PoolBuilder::open`]/[`open_blocking`](PoolBuilder::open_blocking) return
+    /// [`Error::InMemoryMigrationsUnsupported`] if migrations are set without a path.
+    pub fn migrations(mut self, migrations: Vec<Migration>) -> Self {
+        self.migrations = migrations;
+        self
+    }
+
+    /// Load a SQLite extension on every connection in the pool, once each is
+    /// opened. `entry_point` is the extension's entry point function name, or
+    /// `None` to use SQLite's default resolution. Can be called multiple times to
+    /// load several extensions, in order.
+    pub fn load_extension<P: AsRef<Path>>(mut self, dylib_path: P, entry_point: Option<&str>) -> Self {
+        self.extensions.push(ExtensionToLoad {
+            dylib_path: dylib_path.as_ref().to_path_buf(),
+            entry_point: entry_point.map(str::to_string),
+        });
+        self
+    }
+
+    /// How long SQLite should wait on a locked table/database before returning
+    /// `SQLITE_BUSY`, via [`rusqlite::Connection::busy_timeout`], applied to every
+    /// connection in the pool.
+    ///
+    /// In WAL mode a `Pool` typically holds several writer-capable connections;
+    /// a generous busy timeout (together with [`PoolBuilder::retry_on_busy`]) is
+    /// how contention between them is absorbed rather than surfaced to callers.
+    pub fn busy_timeout(mut self, busy_timeout: Duration) -> Self {
+        self.busy_timeout = Some(busy_timeout);
+        self
+    }
+
+    /// Transparently retry `conn`/`conn_blocking` closures that fail with
+    /// `SQLITE_BUSY`/`SQLITE_LOCKED`, up to `max_retries` times, sleeping `backoff`
+    /// between attempts. Applied to every connection in the pool.
+    ///
+    /// See [`ClientBuilder::retry_on_busy`](crate::ClientBuilder::retry_on_busy):
+    /// a retry re-runs the whole closure, so closures should be idempotent or wrap
+    /// their own transaction.
+    pub fn retry_on_busy(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.retry_policy = Some((max_retries, backoff));
+        self
+    }
+
+    fn client_builder(&self) -> ClientBuilder {
+        let mut builder = ClientBuilder::new().flags(self.flags);
+        if let Some(path) = &self.path {
+            builder = builder.path(path);
+        }
+        if let Some(journal_mode) = self.journal_mode {
+            builder = builder.journal_mode(journal_mode);
+        }
+        for extension in &self.extensions {
+            builder = builder.load_extension(&extension.dylib_path, extension.entry_point.as_deref());
+        }
+        if let Some(busy_timeout) = self.busy_timeout {
+            builder = builder.busy_timeout(busy_timeout);
+        }
+        if let Some((max_retries, backoff)) = self.retry_policy {
+            builder = builder.retry_on_busy(max_retries, backoff);
+        }
+        builder
+    }
+
+    /// Open the pool: migrations (if any) are applied exactly once on a dedicated
+    /// connection, then `num_conns` background connections are spawned to serve
+    /// the now up-to-date schema.
+    pub async fn open(self) -> Result<Pool, Error> {
+        self.run_migrations()?;
+        let num_conns = self.num_conns.max(1);
+        let mut clients = Vec::with_capacity(num_conns);
+        for _ in 0..num_conns {
+            clients.push(self.client_builder().open().await?);
+        }
+        Ok(Pool {
+            clients,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Blocking variant of [`PoolBuilder::open`].
+    pub fn open_blocking(self) -> Result<Pool, Error> {
+        self.run_migrations()?;
+        let num_conns = self.num_conns.max(1);
+        let mut clients = Vec::with_capacity(num_conns);
+        for _ in 0..num_conns {
+            clients.push(self.client_builder().open_blocking()?);
+        }
+        Ok(Pool {
+            clients,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    fn run_migrations(&self) -> Result<(), Error> {
+        if self.migrations.is_empty() {
+            return Ok(());
+        }
+        if self.path.is_none() {
+            // An in-memory `Pool` opens a fresh, unconnected in-memory database per
+            // client connection, so migrations run here (on a throwaway connection)
+            // would never be seen by the connections that actually serve `conn`.
+            return Err(Error::InMemoryMigrationsUnsupported);
+        }
+        // Applied once, up front, on a throwaway connection, so that by the time
+        // any of the pool's background connections start serving, the schema is
+        // already at the latest version.
+        open_connection(
+            self.path.as_deref(),
+            self.flags,
+            self.journal_mode,
+            self.busy_timeout,
+            &self.migrations,
+            &[],
+        )?;
+        Ok(())
+    }
+}
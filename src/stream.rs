@@ -0,0 +1,105 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_channel::mpsc;
+use futures_util::sink::SinkExt;
+use futures_util::stream::Stream;
+use rusqlite::{Connection, Params};
+
+use crate::client::Client;
+use crate::error::Error;
+
+/// Buffer size of the channel between the background thread and the consumer.
+/// A `futures` mpsc channel holds this many items plus one for its single sender,
+/// so the background thread pauses once 33 mapped rows are unconsumed.
+const DEFAULT_CHANNEL_CAPACITY: usize = 32;
+
+/// A [`Stream`] of rows produced by [`Client::query_stream`].
+///
+/// Rows are pulled from the background thread in bounded batches: the background
+/// thread pauses once the channel's buffer is full, and resumes as the
+/// stream is polled and items are drained, giving the consumer backpressure.
+pub struct QueryStream<T> {
+    rx: mpsc::Receiver<Result<T, Error>>,
+}
+
+impl<T> Stream for QueryStream<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.rx).poll_next(cx)
+    }
+}
+
+impl Client {
+    /// Run `sql` on the background thread and stream the mapped rows back to the
+    /// caller through a bounded channel, so a query over a very large table can be
+    /// consumed without buffering the whole result set in memory.
+    ///
+    /// `map_fn` is invoked once per row, on the background thread, exactly as it
+    /// would be inside [`Client::conn`]. If the `Client` has already been closed,
+    /// the returned stream yields no items.
+    ///
+    /// The background thread is reserved for this query for as long as the
+    /// returned [`QueryStream`] is alive and not fully drained: it blocks sending
+    /// each row into the channel, so it cannot interleave work from any other
+    /// `conn`/`conn_blocking`/`query_stream` call on this `Client`. Awaiting such a
+    /// call before the stream is drained (or dropped) deadlocks; drain or drop the
+    /// stream first, or use a separate `Client`/`Pool` connection for concurrent
+    /// work.
+    pub fn query_stream<P, F, T>(&self, sql: String, params: P, mut map_fn: F) -> QueryStream<T>
+    where
+        P: Params + Send + 'static,
+        F: FnMut(&rusqlite::Row<'_>) -> rusqlite::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+        let _ = self.execute(Box::new(move |conn| {
+            run_query_stream(conn, &sql, params, &mut map_fn, tx);
+        }));
+        QueryStream { rx }
+    }
+}
+
+fn run_query_stream<P, F, T>(
+    conn: &mut Connection,
+    sql: &str,
+    params: P,
+    map_fn: &mut F,
+    mut tx: mpsc::Sender<Result<T, Error>>,
+) where
+    P: Params,
+    F: FnMut(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
+{
+    let mut stmt = match conn.prepare(sql) {
+        Ok(stmt) => stmt,
+        Err(err) => {
+            let _ = futures_executor::block_on(tx.send(Err(Error::from(err))));
+            return;
+        }
+    };
+    let mut rows = match stmt.query(params) {
+        Ok(rows) => rows,
+        Err(err) => {
+            let _ = futures_executor::block_on(tx.send(Err(Error::from(err))));
+            return;
+        }
+    };
+    loop {
+        let row = match rows.next() {
+            Ok(Some(row)) => row,
+            Ok(None) => return,
+            Err(err) => {
+                let _ = futures_executor::block_on(tx.send(Err(Error::from(err))));
+                return;
+            }
+        };
+        let mapped = map_fn(row).map_err(Error::from);
+        // Blocks the background thread when the channel is full, resuming once the
+        // consumer drains it; this is exactly how backpressure is applied.
+        if futures_executor::block_on(tx.send(mapped)).is_err() {
+            // The consumer dropped the stream; stop pulling further rows.
+            return;
+        }
+    }
+}
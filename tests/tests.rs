@@ -1,4 +1,7 @@
-use obeli_sk_deps_async_sqlite::{ClientBuilder, Error, JournalMode, PoolBuilder};
+use futures_util::{StreamExt, TryStreamExt};
+use obeli_sk_deps_async_sqlite::{
+    BackupOptions, ClientBuilder, Error, JournalMode, Migration, PoolBuilder,
+};
 
 #[test]
 fn test_blocking_client() {
@@ -127,6 +130,139 @@ fn perf_blocking_pool() {
     pool.close_blocking().expect("closing client conn");
 }
 
+#[test]
+fn test_backup_restore_blocking() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new()
+        .journal_mode(JournalMode::Wal)
+        .path(tmp_dir.path().join("sqlite.db"))
+        .open_blocking()
+        .expect("client unable to be opened");
+
+    client
+        .conn_blocking(|conn| {
+            conn.execute(
+                "CREATE TABLE testing (id INTEGER PRIMARY KEY, val TEXT NOT NULL)",
+                (),
+            )?;
+            conn.execute("INSERT INTO testing VALUES (1, ?)", ["value1"])
+        })
+        .expect("writing schema and seed data");
+
+    let backup_path = tmp_dir.path().join("backup.db");
+    client
+        .backup_blocking(&backup_path, BackupOptions::new(), None)
+        .expect("backing up database");
+
+    client
+        .conn_blocking(|conn| conn.execute("INSERT INTO testing VALUES (2, ?)", ["value2"]))
+        .expect("writing additional row");
+
+    client
+        .restore_blocking(&backup_path, BackupOptions::new(), None)
+        .expect("restoring database from backup");
+
+    let count: i64 = client
+        .conn_blocking(|conn| conn.query_row("SELECT COUNT(*) FROM testing", (), |row| row.get(0)))
+        .expect("counting rows after restore");
+    assert_eq!(count, 1);
+
+    client.close_blocking().expect("closing client conn");
+}
+
+#[test]
+fn test_migrations_blocking() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let db_path = tmp_dir.path().join("sqlite.db");
+
+    let migrations = vec![
+        Migration::new(1, "CREATE TABLE testing (id INTEGER PRIMARY KEY, val TEXT NOT NULL)"),
+        Migration::new(2, "ALTER TABLE testing ADD COLUMN extra TEXT"),
+    ];
+
+    let client = ClientBuilder::new()
+        .path(&db_path)
+        .migrations(migrations.clone())
+        .open_blocking()
+        .expect("client unable to be opened");
+
+    let user_version: u32 = client
+        .conn_blocking(|conn| conn.query_row("PRAGMA user_version", (), |row| row.get(0)))
+        .expect("reading user_version");
+    assert_eq!(user_version, 2);
+
+    client
+        .conn_blocking(|conn| {
+            conn.execute(
+                "INSERT INTO testing (id, val, extra) VALUES (1, 'value1', 'extra1')",
+                (),
+            )
+        })
+        .expect("inserting into migrated schema");
+
+    client.close_blocking().expect("closing client conn");
+
+    // Reopening with the same migrations is a no-op: user_version is already
+    // at the latest version, so nothing is re-applied.
+    let client = ClientBuilder::new()
+        .path(&db_path)
+        .migrations(migrations)
+        .open_blocking()
+        .expect("client unable to be reopened");
+    let count: i64 = client
+        .conn_blocking(|conn| conn.query_row("SELECT COUNT(*) FROM testing", (), |row| row.get(0)))
+        .expect("counting rows");
+    assert_eq!(count, 1);
+    client.close_blocking().expect("closing client conn");
+}
+
+#[test]
+fn test_load_extension_missing_file_blocking() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new()
+        .path(tmp_dir.path().join("sqlite.db"))
+        .open_blocking()
+        .expect("client unable to be opened");
+
+    let err = client
+        .load_extension_blocking(tmp_dir.path().join("does-not-exist.so"), None)
+        .expect_err("loading a nonexistent extension should fail");
+    assert!(matches!(err, Error::Rusqlite(_)));
+
+    client.close_blocking().expect("closing client conn");
+}
+
+#[test]
+fn test_busy_timeout_and_retry_on_busy_blocking() {
+    use std::time::Duration;
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new()
+        .journal_mode(JournalMode::Wal)
+        .path(tmp_dir.path().join("sqlite.db"))
+        .busy_timeout(Duration::from_millis(100))
+        .retry_on_busy(5, Duration::from_millis(10))
+        .open_blocking()
+        .expect("client unable to be opened");
+
+    client
+        .conn_blocking(|conn| {
+            conn.execute(
+                "CREATE TABLE testing (id INTEGER PRIMARY KEY, val TEXT NOT NULL)",
+                (),
+            )?;
+            conn.execute("INSERT INTO testing VALUES (1, ?)", ["value1"])
+        })
+        .expect("writing schema and seed data");
+
+    let val: String = client
+        .conn_blocking(|conn| conn.query_row("SELECT val FROM testing WHERE id=?", [1], |row| row.get(0)))
+        .expect("querying for result");
+    assert_eq!(val, "value1");
+
+    client.close_blocking().expect("closing client conn");
+}
+
 macro_rules! async_test {
     ($name:ident) => {
         paste::item! {
@@ -150,6 +286,11 @@ macro_rules! async_test {
 async_test!(test_journal_mode);
 async_test!(test_concurrency);
 async_test!(test_pool);
+async_test!(test_query_stream);
+async_test!(test_subscribe_updates);
+async_test!(test_subscribe_commits);
+async_test!(test_subscribe_rollbacks);
+async_test!(test_open_blob);
 
 async fn test_journal_mode() {
     let tmp_dir = tempfile::tempdir().unwrap();
@@ -200,6 +341,198 @@ async fn test_concurrency() {
         .expect("collecting query results");
 }
 
+async fn test_query_stream() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new()
+        .path(tmp_dir.path().join("sqlite.db"))
+        .open()
+        .await
+        .expect("client unable to be opened");
+
+    client
+        .conn(|conn| {
+            conn.execute(
+                "CREATE TABLE testing (id INTEGER PRIMARY KEY, val TEXT NOT NULL)",
+                (),
+            )?;
+            for id in 0..100 {
+                conn.execute(
+                    "INSERT INTO testing VALUES (?, ?)",
+                    obeli_sk_deps_async_sqlite::rusqlite::params![id, format!("value{id}")],
+                )?;
+            }
+            Ok(())
+        })
+        .await
+        .expect("writing schema and seed data");
+
+    let values: Vec<String> = client
+        .query_stream(
+            "SELECT val FROM testing ORDER BY id".to_string(),
+            (),
+            |row| row.get(0),
+        )
+        .try_collect()
+        .await
+        .expect("streaming query results");
+
+    assert_eq!(values.len(), 100);
+    assert_eq!(values[0], "value0");
+    assert_eq!(values[99], "value99");
+}
+
+async fn test_subscribe_updates() {
+    use obeli_sk_deps_async_sqlite::UpdateAction;
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new()
+        .path(tmp_dir.path().join("sqlite.db"))
+        .open()
+        .await
+        .expect("client unable to be opened");
+
+    client
+        .conn(|conn| {
+            conn.execute(
+                "CREATE TABLE testing (id INTEGER PRIMARY KEY, val TEXT NOT NULL)",
+                (),
+            )
+        })
+        .await
+        .expect("writing schema");
+
+    let mut updates = client
+        .subscribe_updates()
+        .await
+        .expect("subscribing to updates");
+
+    client
+        .conn(|conn| conn.execute("INSERT INTO testing VALUES (1, ?)", ["value1"]))
+        .await
+        .expect("inserting row");
+
+    let event = updates.next().await.expect("update event");
+    assert_eq!(event.action, UpdateAction::Insert);
+    assert_eq!(event.table, "testing");
+    assert_eq!(event.rowid, 1);
+}
+
+async fn test_subscribe_commits() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new()
+        .path(tmp_dir.path().join("sqlite.db"))
+        .open()
+        .await
+        .expect("client unable to be opened");
+
+    client
+        .conn(|conn| {
+            conn.execute(
+                "CREATE TABLE testing (id INTEGER PRIMARY KEY, val TEXT NOT NULL)",
+                (),
+            )
+        })
+        .await
+        .expect("writing schema");
+
+    let mut commits = client
+        .subscribe_commits()
+        .await
+        .expect("subscribing to commits");
+
+    client
+        .conn(|conn| conn.execute("INSERT INTO testing VALUES (1, ?)", ["value1"]))
+        .await
+        .expect("inserting row");
+
+    commits.next().await.expect("commit event");
+}
+
+async fn test_subscribe_rollbacks() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new()
+        .path(tmp_dir.path().join("sqlite.db"))
+        .open()
+        .await
+        .expect("client unable to be opened");
+
+    client
+        .conn(|conn| {
+            conn.execute(
+                "CREATE TABLE testing (id INTEGER PRIMARY KEY, val TEXT NOT NULL)",
+                (),
+            )
+        })
+        .await
+        .expect("writing schema");
+
+    let mut rollbacks = client
+        .subscribe_rollbacks()
+        .await
+        .expect("subscribing to rollbacks");
+
+    client
+        .conn(|conn| {
+            conn.execute_batch("BEGIN; INSERT INTO testing VALUES (1, 'value1'); ROLLBACK;")
+        })
+        .await
+        .expect("rolling back transaction");
+
+    rollbacks.next().await.expect("rollback event");
+
+    let count: i64 = client
+        .conn(|conn| conn.query_row("SELECT COUNT(*) FROM testing", (), |row| row.get(0)))
+        .await
+        .expect("counting rows");
+    assert_eq!(count, 0);
+}
+
+async fn test_open_blob() {
+    use futures_util::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let client = ClientBuilder::new()
+        .path(tmp_dir.path().join("sqlite.db"))
+        .open()
+        .await
+        .expect("client unable to be opened");
+
+    client
+        .conn(|conn| {
+            conn.execute(
+                "CREATE TABLE testing (id INTEGER PRIMARY KEY, val BLOB NOT NULL)",
+                (),
+            )?;
+            conn.execute("INSERT INTO testing VALUES (1, ?)", [vec![0u8; 16]])
+        })
+        .await
+        .expect("writing schema and seed data");
+
+    let mut blob = client
+        .open_blob("main", "testing", "val", 1, false)
+        .await
+        .expect("opening blob handle");
+    assert_eq!(blob.len(), 16);
+
+    blob.write_all(b"hello").await.expect("writing to blob");
+    blob.seek(std::io::SeekFrom::Start(0))
+        .await
+        .expect("seeking to start");
+
+    let mut buf = vec![0u8; 5];
+    blob.read_exact(&mut buf).await.expect("reading from blob");
+    assert_eq!(&buf, b"hello");
+
+    drop(blob);
+
+    let stored: Vec<u8> = client
+        .conn(|conn| conn.query_row("SELECT val FROM testing WHERE id=1", (), |row| row.get(0)))
+        .await
+        .expect("reading back full blob");
+    assert_eq!(&stored[..5], b"hello");
+    assert_eq!(stored.len(), 16);
+}
+
 async fn test_pool() {
     let tmp_dir = tempfile::tempdir().unwrap();
     let pool = PoolBuilder::new()